@@ -0,0 +1,6 @@
+CREATE TABLE "posts" (
+  "title" VARCHAR(20) UNIQUE,
+  "views" NUMERIC,
+  "secret" TEXT,
+  "comment" TEXT
+);
\ No newline at end of file
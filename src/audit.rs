@@ -0,0 +1,269 @@
+use crate::encryption::EncryptionKey;
+use crate::io::{fetch_file, save_file};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    CREATE,
+    UPDATE,
+    DELETE,
+    SCHEMA,
+}
+
+impl Default for Action {
+    fn default() -> Self {
+        Action::UPDATE
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct AuditEntry {
+    pub id: String,
+    user_id: String,
+    timestamp: u64,
+    action: Action,
+    target: String,
+    details: String,
+}
+
+impl AuditEntry {
+    fn create_no_check(
+        id: &str,
+        user_id: &str,
+        timestamp: u64,
+        action: Action,
+        target: &str,
+        details: &str,
+    ) -> AuditEntry {
+        AuditEntry {
+            id: String::from(id),
+            user_id: String::from(user_id),
+            timestamp,
+            action,
+            target: String::from(target),
+            details: String::from(details),
+        }
+    }
+
+    pub fn create(
+        all_entries: &mut Vec<AuditEntry>,
+        user_id: &str,
+        action_txt: &str,
+        target: &str,
+        details: &str,
+    ) -> Result<(), String> {
+        let action = match action_txt {
+            "create" => Action::CREATE,
+            "update" => Action::UPDATE,
+            "delete" => Action::DELETE,
+            "schema" => Action::SCHEMA,
+            _ => return Err(String::from("Error: action is invalid")),
+        };
+
+        if String::from(user_id.trim()).len() < 1 {
+            return Err(String::from(
+                "Error: user_id does not contain enough characters",
+            ));
+        } else if !user_id.chars().all(|c| c != ';' && c != '\n' && c != '\r') {
+            return Err(String::from("Error: user_id contains an invalid character"));
+        }
+
+        if String::from(target.trim()).len() < 1 {
+            return Err(String::from(
+                "Error: target does not contain enough characters",
+            ));
+        } else if !target.chars().all(|c| c != ';' && c != '\n' && c != '\r') {
+            return Err(String::from("Error: target contains an invalid character"));
+        }
+
+        if !details.chars().all(|c| c != ';' && c != '\n' && c != '\r') {
+            return Err(String::from("Error: details contains an invalid character"));
+        } else if String::from(details.trim()).len() > 400 {
+            return Err(String::from("Error: details contains too many characters"));
+        }
+
+        let new_entry = AuditEntry {
+            id: EncryptionKey::generate_uuid(8),
+            user_id: user_id.trim().to_string(),
+            timestamp: current_timestamp(),
+            action,
+            target: target.trim().to_string(),
+            details: details.trim().to_string(),
+        };
+        all_entries.push(new_entry);
+
+        Ok(())
+    }
+
+    /// Checks the actor before a mutation is applied, so that the entry
+    /// recorded afterwards cannot be rejected. Nothing is checked when the
+    /// mutation is not audited.
+    pub fn check_actor(
+        all_entries: &Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if all_entries.is_none() {
+            return Ok(());
+        }
+
+        if String::from(actor.trim()).len() < 1 {
+            return Err(String::from(
+                "Error: actor does not contain enough characters",
+            ));
+        } else if !actor.chars().all(|c| c != ';' && c != '\n' && c != '\r') {
+            return Err(String::from("Error: actor contains an invalid character"));
+        }
+
+        Ok(())
+    }
+
+    /// Logs a mutation that has already been applied. Mutators call
+    /// `check_actor` first, so a failure here is only printed.
+    pub fn record(
+        all_entries: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+        action_txt: &str,
+        target: &str,
+        details: &str,
+    ) {
+        if let Some(all_entries) = all_entries {
+            let create_entry = Self::create(all_entries, actor, action_txt, target, details);
+            if let Err(e) = create_entry {
+                println!("{}", e);
+            }
+        }
+    }
+
+    /// Returns the entries matching every non-empty filter, oldest first.
+    /// `since` is a UNIX timestamp in seconds (0 to include everything).
+    pub fn query(
+        all_entries: &Vec<AuditEntry>,
+        user_id: &str,
+        target: &str,
+        since: u64,
+    ) -> Vec<AuditEntry> {
+        all_entries
+            .iter()
+            .filter(|entry| user_id.is_empty() || entry.user_id == user_id)
+            .filter(|entry| target.is_empty() || entry.target == target)
+            .filter(|entry| entry.timestamp >= since)
+            .cloned()
+            .collect::<Vec<AuditEntry>>()
+    }
+
+    /// Drops entries older than `max_age` seconds and then keeps at most
+    /// `max_entries` of the most recent ones. A value of 0 disables that rule.
+    pub fn apply_retention(all_entries: &mut Vec<AuditEntry>, max_entries: usize, max_age: u64) {
+        if max_age > 0 {
+            let cutoff = current_timestamp().saturating_sub(max_age);
+            all_entries.retain(|entry| entry.timestamp >= cutoff);
+        }
+
+        if max_entries > 0 && all_entries.len() > max_entries {
+            let excess = all_entries.len() - max_entries;
+            all_entries.drain(0..excess);
+        }
+    }
+
+    pub fn get_action(self: &Self) -> Action {
+        self.action.clone()
+    }
+
+    pub fn get_target(self: &Self) -> String {
+        self.target.clone()
+    }
+
+    pub fn get_timestamp(self: &Self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn to_string(entry: AuditEntry) -> String {
+        let action_txt = match entry.action {
+            Action::CREATE => "create",
+            Action::UPDATE => "update",
+            Action::DELETE => "delete",
+            Action::SCHEMA => "schema",
+        };
+
+        format!(
+            "{};{};{};{};{};{}",
+            entry.id, entry.user_id, entry.timestamp, action_txt, entry.target, entry.details
+        )
+    }
+
+    pub fn from_string(entry_str: &str) -> Result<AuditEntry, String> {
+        let current_entry = entry_str.split(";").collect::<Vec<&str>>();
+
+        if current_entry.len() < 6 {
+            return Err(String::from("Error: Wrong format for Audit Entry data"));
+        }
+
+        let parsed_timestamp = current_entry[2].parse::<u64>();
+        if let Err(e) = parsed_timestamp {
+            return Err(format!("Error when parsing timestamp: {}", e));
+        }
+
+        let action = match current_entry[3] {
+            "create" => Action::CREATE,
+            "update" => Action::UPDATE,
+            "delete" => Action::DELETE,
+            "schema" => Action::SCHEMA,
+            _ => return Err(String::from("Error: action is invalid")),
+        };
+
+        Ok(AuditEntry::create_no_check(
+            current_entry[0],
+            current_entry[1],
+            parsed_timestamp.unwrap(),
+            action,
+            current_entry[4],
+            current_entry[5],
+        ))
+    }
+}
+
+fn current_timestamp() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs(),
+        Err(_) => 0,
+    }
+}
+
+pub fn fetch_all_audit_entries(path: String, encryption_key: &String) -> Vec<AuditEntry> {
+    let all_entries_raw = fetch_file(path.clone(), encryption_key);
+
+    let individual_entries = all_entries_raw
+        .split("\n")
+        .filter(|line| line.chars().count() >= 3);
+
+    let mut final_entries: Vec<AuditEntry> = Vec::<AuditEntry>::new();
+
+    for entry in individual_entries {
+        match AuditEntry::from_string(entry) {
+            Ok(tmp_entry) => final_entries.push(tmp_entry),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    final_entries
+}
+
+pub fn save_all_audit_entries(entries: &Vec<AuditEntry>, path: String, encryption_key: &String) {
+    let mut stringified_entries = String::new();
+
+    for entry in entries {
+        stringified_entries = format!(
+            "{}{}{}",
+            stringified_entries,
+            if stringified_entries.chars().count() > 1 {
+                "\n"
+            } else {
+                ""
+            },
+            AuditEntry::to_string(entry.clone()),
+        );
+    }
+
+    save_file(path, stringified_entries, encryption_key);
+    println!("Audit entries saved!");
+}
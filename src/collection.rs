@@ -1,3 +1,4 @@
+use crate::audit::AuditEntry;
 use crate::custom_structures::CustomStructure;
 use crate::io::{fetch_file, save_file};
 use crate::structures::{try_add_structure, Structure};
@@ -20,7 +21,13 @@ impl Collection {
         project_id: &str,
         name: &str,
         description: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         // if Self::exist(collections, id) {
         //     let new_id = EncryptionKey::generate_uuid();
         //     return Self::create(
@@ -47,7 +54,7 @@ impl Collection {
         };
         collections.push(new_collection);
 
-        let id_update = Self::update_id(collections, &tmp_id, id);
+        let id_update = Self::update_id(collections, &tmp_id, id, None, actor);
         if let Err(e) = id_update {
             has_error = true;
             println!("Error: {}", e);
@@ -56,7 +63,8 @@ impl Collection {
         }
 
         if !has_error {
-            let project_id_update = Self::update_project_id(collections, &new_id, project_id);
+            let project_id_update =
+                Self::update_project_id(collections, &new_id, project_id, None, actor);
             if let Err(e) = project_id_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -65,7 +73,7 @@ impl Collection {
         }
 
         if !has_error {
-            let name_update = Self::update_name(collections, &new_id, name);
+            let name_update = Self::update_name(collections, &new_id, name, None, actor);
             if let Err(e) = name_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -74,7 +82,8 @@ impl Collection {
         }
 
         if !has_error {
-            let description_update = Self::update_description(collections, &new_id, description);
+            let description_update =
+                Self::update_description(collections, &new_id, description, None, actor);
             if let Err(e) = description_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -83,7 +92,7 @@ impl Collection {
         }
 
         if has_error {
-            let delete_collection = Self::delete(collections, &new_id);
+            let delete_collection = Self::delete(collections, &new_id, None, actor);
             if let Err(e) = delete_collection {
                 println!("Error: {}", e);
             }
@@ -91,6 +100,8 @@ impl Collection {
             return Err(latest_error);
         }
 
+        AuditEntry::record(audit, actor, "schema", "collections", &new_id);
+
         Ok(())
     }
 
@@ -110,7 +121,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         new_id: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -144,6 +161,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} id {}", id, new_id.trim()),
+        );
+
         Ok(())
     }
 
@@ -151,7 +176,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         project_id: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         if !String::from(project_id)
@@ -185,6 +216,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} project_id", id),
+        );
+
         Ok(())
     }
 
@@ -192,7 +231,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         name: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         if !String::from(name)
@@ -222,6 +267,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} name", id),
+        );
+
         Ok(())
     }
 
@@ -229,7 +282,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         description: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         if !String::from(description)
@@ -263,6 +322,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} description", id),
+        );
+
         Ok(())
     }
 
@@ -270,7 +337,14 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         structure: Structure,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
+        let structure_id = structure.id.clone();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -289,6 +363,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} add_structure {}", id, structure_id),
+        );
+
         Ok(())
     }
 
@@ -296,7 +378,14 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         structure: Structure,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
+        let structure_id = structure.id.clone();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -327,6 +416,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} update_structure {}", id, structure_id),
+        );
+
         Ok(())
     }
 
@@ -334,7 +431,14 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         custom_structure: CustomStructure,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
+        let custom_structure_id = custom_structure.id.clone();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -353,6 +457,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} add_custom_structure {}", id, custom_structure_id),
+        );
+
         Ok(())
     }
 
@@ -360,7 +472,14 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         custom_structure: CustomStructure,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
+        let custom_structure_id = custom_structure.id.clone();
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -391,6 +510,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} update_custom_structure {}", id, custom_structure_id),
+        );
+
         Ok(())
     }
 
@@ -398,7 +525,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         structures: Vec<Structure>,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -414,6 +547,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} structures", id),
+        );
+
         Ok(())
     }
 
@@ -421,7 +562,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         custom_structures: Vec<CustomStructure>,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -437,6 +584,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} custom_structures", id),
+        );
+
         Ok(())
     }
 
@@ -444,7 +599,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         structure_id: &String,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -467,6 +628,14 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} remove_structure {}", id, structure_id),
+        );
+
         Ok(())
     }
 
@@ -474,7 +643,13 @@ impl Collection {
         all_collections: &mut Vec<Collection>,
         id: &String,
         custom_structure_id: &String,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -497,10 +672,77 @@ impl Collection {
             return Err(String::from("Error: Collection not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} remove_custom_structure {}", id, custom_structure_id),
+        );
+
+        Ok(())
+    }
+
+    pub fn clone_collection(
+        all_collections: &mut Vec<Collection>,
+        id: &String,
+        new_id: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
+        let mut found_collection: Option<Collection> = None;
+
+        for collection in all_collections.iter() {
+            if collection.id == *id {
+                found_collection = Some(collection.clone());
+                break;
+            }
+        }
+
+        if let None = found_collection {
+            return Err(String::from("Error: Collection not found"));
+        }
+
+        let tmp_id = String::from("test;");
+        let mut new_collection = found_collection.unwrap();
+        new_collection.id = tmp_id.clone();
+        all_collections.push(new_collection);
+
+        let id_update = Self::update_id(all_collections, &tmp_id, new_id, None, actor);
+        if let Err(e) = id_update {
+            let delete_collection = Self::delete(all_collections, &tmp_id, None, actor);
+            if let Err(e) = delete_collection {
+                println!("Error: {}", e);
+            }
+
+            return Err(e);
+        }
+
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} clone {}", id, new_id.trim()),
+        );
+
         Ok(())
     }
 
-    pub fn delete(all_collections: &mut Vec<Collection>, id: &String) -> Result<(), String> {
+    pub fn delete(
+        all_collections: &mut Vec<Collection>,
+        id: &String,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_collection: Option<Collection> = None;
 
         for collection in all_collections.iter_mut() {
@@ -529,6 +771,14 @@ impl Collection {
 
         *all_collections = updated_collections;
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "schema",
+            "collections",
+            &format!("{} delete", id),
+        );
+
         Ok(())
     }
 
@@ -549,6 +799,24 @@ impl Collection {
         )
     }
 
+    pub fn to_sql(collection: Collection) -> Result<String, String> {
+        if collection.structures.len() < 1 {
+            return Err(String::from("Error: Collection has no structures"));
+        }
+
+        let columns = collection
+            .structures
+            .iter()
+            .map(|structure| format!("  {}", Structure::to_sql(structure.clone())))
+            .collect::<Vec<String>>();
+
+        Ok(format!(
+            "CREATE TABLE \"{}\" (\n{}\n);",
+            collection.id,
+            columns.join(",\n")
+        ))
+    }
+
     pub fn from_string(mut all_collections: &mut Vec<Collection>, collection_str: &str) -> String {
         let current_collection = collection_str.split(";").collect::<Vec<&str>>();
 
@@ -559,6 +827,8 @@ impl Collection {
             current_collection[1],
             current_collection[2],
             current_collection[3].split(">").collect::<Vec<&str>>()[0],
+            None,
+            "",
         );
         if let Err(e) = create_collection {
             return e;
@@ -620,6 +890,8 @@ impl Collection {
             &mut all_collections,
             &collection_id.to_string(),
             final_structures,
+            None,
+            "",
         );
         if let Err(e) = set_structures {
             return e;
@@ -629,6 +901,8 @@ impl Collection {
             &mut all_collections,
             &collection_id.to_string(),
             final_custom_structures,
+            None,
+            "",
         );
         if let Err(e) = set_custom_structures {
             return e;
@@ -673,3 +947,32 @@ pub fn save_all_collections(collections: &Vec<Collection>, path: String, encrypt
     save_file(path, stringified_collections, encryption_key);
     println!("Collections saved!");
 }
+
+pub fn export_sql(collections: &Vec<Collection>, path: String) {
+    let mut stringified_collections = String::new();
+
+    for collection in collections {
+        // Collections without structures would produce an invalid table
+        let statement = match Collection::to_sql(collection.clone()) {
+            Ok(statement) => statement,
+            Err(e) => {
+                println!("{} ({})", e, collection.id);
+                continue;
+            }
+        };
+
+        stringified_collections = format!(
+            "{}{}{}",
+            stringified_collections,
+            if stringified_collections.chars().count() > 1 {
+                "\n\n"
+            } else {
+                ""
+            },
+            statement
+        );
+    }
+
+    save_file(path, stringified_collections, &String::new());
+    println!("Collections exported!");
+}
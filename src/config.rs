@@ -1,3 +1,4 @@
+use crate::audit::AuditEntry;
 use crate::io::{fetch_file, save_file};
 
 #[derive(Default, Debug, Clone)]
@@ -26,7 +27,17 @@ impl Config {
         found
     }
 
-    pub fn create(all_configs: &mut Vec<Config>, name: &str, value: &str) -> Result<(), String> {
+    pub fn create(
+        all_configs: &mut Vec<Config>,
+        name: &str,
+        value: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         if !String::from(name)
             .chars()
             .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
@@ -57,15 +68,17 @@ impl Config {
         };
         all_configs.push(new_config);
 
-        let update_value_result = Self::update_value(all_configs, name, value);
+        let update_value_result = Self::update_value(all_configs, name, value, None, actor);
         if let Err(e) = update_value_result {
-            let delete_result = Self::delete(all_configs, name);
+            let delete_result = Self::delete(all_configs, name, None, actor);
             if let Err(e) = delete_result {
                 return Err(e);
             }
             return Err(e);
         }
 
+        AuditEntry::record(audit, actor, "create", "configs", name);
+
         Ok(())
     }
 
@@ -73,7 +86,13 @@ impl Config {
         all_configs: &mut Vec<Config>,
         name: &str,
         value: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_config: Option<Config> = None;
 
         if String::from(value).chars().any(|c| c == '|') {
@@ -100,10 +119,21 @@ impl Config {
             return Err(String::from("Error: Config not found"));
         }
 
+        AuditEntry::record(audit, actor, "update", "configs", name);
+
         Ok(())
     }
 
-    pub fn delete(all_configs: &mut Vec<Config>, name: &str) -> Result<(), String> {
+    pub fn delete(
+        all_configs: &mut Vec<Config>,
+        name: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_config: Option<Config> = None;
 
         for config in all_configs.iter_mut() {
@@ -128,6 +158,8 @@ impl Config {
 
         *all_configs = updated_configs;
 
+        AuditEntry::record(audit, actor, "delete", "configs", name);
+
         Ok(())
     }
 
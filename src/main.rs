@@ -3,6 +3,7 @@
 extern crate magic_crypt;
 extern crate argon2;
 
+use audit::{fetch_all_audit_entries, save_all_audit_entries, AuditEntry};
 use collection::{fetch_all_collections, save_all_collections, Collection};
 use config::{fetch_all_configs, save_all_configs, Config};
 use custom_structures::CustomStructure;
@@ -13,6 +14,7 @@ use project::{fetch_all_projects, save_all_projects, Project};
 use structures::Structure;
 use user::{fetch_all_users, save_all_users, User};
 
+mod audit;
 mod collection;
 mod config;
 mod custom_structures;
@@ -26,6 +28,9 @@ mod user;
 
 const MAPPINGS_PATH: &str = "data/mappings.txt";
 const TMP_PASSWORD: &str = "Test123*";
+const SYSTEM_ACTOR: &str = "system";
+const DEFAULT_AUDIT_MAX_ENTRIES: usize = 10000;
+const DEFAULT_AUDIT_MAX_AGE: u64 = 0;
 
 fn main() {
     initialize();
@@ -33,10 +38,13 @@ fn main() {
 
 fn initialize() {
     let all_mappings = initialize_mappings();
-    let all_users: Vec<User> = initialize_users(&all_mappings);
-    let all_projects: Vec<Project> = initialize_projects(&all_mappings);
-    let _all_configs: Vec<Config> = initialize_configs(&all_mappings);
-    let all_collections: Vec<Collection> = initialize_collections(&all_mappings);
+    let mut all_audit_entries: Vec<AuditEntry> = initialize_audit(&all_mappings);
+    let all_users: Vec<User> = initialize_users(&all_mappings, &mut all_audit_entries);
+    let all_projects: Vec<Project> = initialize_projects(&all_mappings, &mut all_audit_entries);
+    let all_configs: Vec<Config> = initialize_configs(&all_mappings, &mut all_audit_entries);
+    let all_collections: Vec<Collection> =
+        initialize_collections(&all_mappings, &mut all_audit_entries);
+    finalize_audit(&all_mappings, &all_configs, &mut all_audit_entries);
 
     println!("{:#?}", User::login(&all_users, "EdgeKing810", "Test123*"));
 
@@ -89,11 +97,18 @@ fn initialize_mappings() -> Vec<Mapping> {
         }
     }
 
+    if !Mapping::exist(&fetched_mappings, "audit") {
+        let audit_mapping = Mapping::create(&mut fetched_mappings, "audit", "data/audit.txt");
+        if let Err(e) = audit_mapping {
+            println!("{}", e);
+        }
+    }
+
     save_all_mappings(&fetched_mappings, MAPPINGS_PATH, &String::from(""));
     fetched_mappings
 }
 
-fn initialize_users(mappings: &Vec<Mapping>) -> Vec<User> {
+fn initialize_users(mappings: &Vec<Mapping>, audit: &mut Vec<AuditEntry>) -> Vec<User> {
     let all_users_path = get_file_name("users", mappings);
     let mut all_users = Vec::<User>::new();
 
@@ -116,6 +131,8 @@ fn initialize_users(mappings: &Vec<Mapping>) -> Vec<User> {
             "kishan@konnect.dev",
             "Test123*",
             0,
+            Some(audit),
+            SYSTEM_ACTOR,
         );
         if let Err(e) = create_user {
             println!("{}", e);
@@ -131,7 +148,7 @@ fn initialize_users(mappings: &Vec<Mapping>) -> Vec<User> {
     all_users
 }
 
-fn initialize_projects(mappings: &Vec<Mapping>) -> Vec<Project> {
+fn initialize_projects(mappings: &Vec<Mapping>, audit: &mut Vec<AuditEntry>) -> Vec<Project> {
     let all_projects_path = get_file_name("projects", mappings);
     let mut all_projects = Vec::<Project>::new();
 
@@ -152,6 +169,8 @@ fn initialize_projects(mappings: &Vec<Mapping>) -> Vec<Project> {
             "Konnect - Social Media",
             "A next-gen social media.",
             "/api/v2/konnect",
+            Some(audit),
+            SYSTEM_ACTOR,
         );
         if let Err(e) = create_project {
             println!("{}", e);
@@ -167,7 +186,7 @@ fn initialize_projects(mappings: &Vec<Mapping>) -> Vec<Project> {
     all_projects
 }
 
-fn initialize_configs(mappings: &Vec<Mapping>) -> Vec<Config> {
+fn initialize_configs(mappings: &Vec<Mapping>, audit: &mut Vec<AuditEntry>) -> Vec<Config> {
     let all_configs_path = get_file_name("configs", mappings);
     let mut all_configs = Vec::<Config>::new();
 
@@ -203,11 +222,14 @@ fn initialize_configs(mappings: &Vec<Mapping>) -> Vec<Config> {
         "UPLOAD_SIZE",
         "SHOULD_INITIALIZE",
         "CORS_WHITELIST",
+        "AUDIT_MAX_ENTRIES",
+        "AUDIT_MAX_AGE",
     ];
 
     for key in config_keys_template {
         if !Config::exist(&all_configs, key) {
-            let create_config = Config::create(&mut all_configs, key, "_empty");
+            let create_config =
+                Config::create(&mut all_configs, key, "_empty", Some(audit), SYSTEM_ACTOR);
             if let Err(e) = create_config {
                 println!("{}", e);
             }
@@ -253,7 +275,7 @@ fn initialize_encryption_key(mappings: &Vec<Mapping>, password: &str) -> Result<
     Ok(encryption_key.unwrap())
 }
 
-fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
+fn initialize_collections(mappings: &Vec<Mapping>, audit: &mut Vec<AuditEntry>) -> Vec<Collection> {
     let all_collections_path = get_file_name("collections", mappings);
     let mut all_collections = Vec::<Collection>::new();
 
@@ -271,6 +293,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "konnect",
             "Posts",
             "To store blog posts.",
+            Some(audit),
+            SYSTEM_ACTOR,
         );
         if let Err(e) = create_collection {
             println!("{}", e);
@@ -283,6 +307,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "Title",
             "text",
             "test title",
+            0,
+            0,
             5,
             20,
             false,
@@ -298,6 +324,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "media",
             "https://test.image.com",
             0,
+            0,
+            0,
             200,
             false,
             false,
@@ -311,6 +339,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "Content",
             "richtext",
             "[ Content goes here ]",
+            0,
+            0,
             30,
             2000,
             false,
@@ -327,6 +357,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "0",
             0,
             9999,
+            0,
+            0,
             false,
             false,
             "",
@@ -340,6 +372,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "comment",
             "0",
             0,
+            0,
+            0,
             9999,
             false,
             false,
@@ -354,6 +388,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "boolean",
             "false",
             0,
+            0,
+            0,
             5,
             false,
             false,
@@ -361,8 +397,14 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             true,
         )
         .unwrap();
-        Collection::set_structures(&mut all_collections, &"posts".to_string(), all_structures)
-            .unwrap();
+        Collection::set_structures(
+            &mut all_collections,
+            &"posts".to_string(),
+            all_structures,
+            Some(audit),
+            SYSTEM_ACTOR,
+        )
+        .unwrap();
 
         let mut all_custom_structures = Vec::<CustomStructure>::new();
         let mut tmp_structures = Vec::<Structure>::new();
@@ -373,6 +415,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "UID",
             "uid",
             "",
+            0,
+            0,
             5,
             20,
             false,
@@ -387,6 +431,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             "Value",
             "text",
             "",
+            0,
+            0,
             1,
             100,
             false,
@@ -407,6 +453,8 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
             &mut all_collections,
             &"posts".to_string(),
             all_custom_structures,
+            Some(audit),
+            SYSTEM_ACTOR,
         )
         .unwrap();
 
@@ -420,6 +468,53 @@ fn initialize_collections(mappings: &Vec<Mapping>) -> Vec<Collection> {
     all_collections
 }
 
+fn initialize_audit(mappings: &Vec<Mapping>) -> Vec<AuditEntry> {
+    let all_audit_path = get_file_name("audit", mappings);
+
+    if let Err(e) = all_audit_path {
+        println!("{}", e);
+        return Vec::<AuditEntry>::new();
+    }
+
+    fetch_all_audit_entries(all_audit_path.unwrap(), &get_encryption_key(&mappings))
+}
+
+fn finalize_audit(
+    mappings: &Vec<Mapping>,
+    configs: &Vec<Config>,
+    all_audit_entries: &mut Vec<AuditEntry>,
+) {
+    let all_audit_path = get_file_name("audit", mappings);
+
+    if let Err(e) = all_audit_path {
+        println!("{}", e);
+        return;
+    }
+
+    let max_entries = get_config_value(configs, "AUDIT_MAX_ENTRIES", DEFAULT_AUDIT_MAX_ENTRIES);
+    let max_age = get_config_value(configs, "AUDIT_MAX_AGE", DEFAULT_AUDIT_MAX_AGE);
+
+    AuditEntry::apply_retention(all_audit_entries, max_entries, max_age);
+    save_all_audit_entries(
+        all_audit_entries,
+        all_audit_path.unwrap(),
+        &get_encryption_key(&mappings),
+    );
+}
+
+fn get_config_value<T: std::str::FromStr>(configs: &Vec<Config>, name: &str, default: T) -> T {
+    match configs.iter().find(|config| config.name == name) {
+        Some(config) if config.value != "_empty" => match config.value.parse::<T>() {
+            Ok(value) => value,
+            Err(_) => {
+                println!("Error: {} is not a valid number, using the default", name);
+                default
+            }
+        },
+        _ => default,
+    }
+}
+
 fn get_encryption_key(all_mappings: &Vec<Mapping>) -> String {
     let init_encryption = initialize_encryption_key(&all_mappings, TMP_PASSWORD);
 
@@ -1,3 +1,4 @@
+use crate::audit::AuditEntry;
 use crate::io::{fetch_file, save_file};
 
 #[derive(Default, Debug, Clone)]
@@ -36,7 +37,13 @@ impl Project {
         name: &str,
         description: &str,
         api_path: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let tmp_id = String::from("test;");
         let mut new_id = String::from(id);
 
@@ -51,7 +58,7 @@ impl Project {
         };
         all_projects.push(new_project);
 
-        let id_update = Self::update_id(all_projects, &tmp_id, id);
+        let id_update = Self::update_id(all_projects, &tmp_id, id, None, actor);
         if let Err(e) = id_update {
             has_error = true;
             println!("Error: {}", e);
@@ -60,7 +67,7 @@ impl Project {
         }
 
         if !has_error {
-            let name_update = Self::update_name(all_projects, &new_id, name);
+            let name_update = Self::update_name(all_projects, &new_id, name, None, actor);
             if let Err(e) = name_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -69,7 +76,8 @@ impl Project {
         }
 
         if !has_error {
-            let description_update = Self::update_description(all_projects, &new_id, description);
+            let description_update =
+                Self::update_description(all_projects, &new_id, description, None, actor);
             if let Err(e) = description_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -78,7 +86,8 @@ impl Project {
         }
 
         if !has_error {
-            let api_path_update = Self::update_api_path(all_projects, &new_id, api_path);
+            let api_path_update =
+                Self::update_api_path(all_projects, &new_id, api_path, None, actor);
             if let Err(e) = api_path_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -87,7 +96,7 @@ impl Project {
         }
 
         if has_error {
-            let delete_project = Self::delete(all_projects, &new_id);
+            let delete_project = Self::delete(all_projects, &new_id, None, actor);
             if let Err(e) = delete_project {
                 println!("Error: {}", e);
             }
@@ -95,6 +104,8 @@ impl Project {
             return Err(latest_error);
         }
 
+        AuditEntry::record(audit, actor, "create", "projects", &new_id);
+
         Ok(())
     }
 
@@ -102,7 +113,13 @@ impl Project {
         all_projects: &mut Vec<Project>,
         id: &String,
         new_id: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_project: Option<Project> = None;
 
         for project in all_projects.iter() {
@@ -136,6 +153,14 @@ impl Project {
             return Err(String::from("Error: Project not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "update",
+            "projects",
+            &format!("{} id {}", id, new_id.trim()),
+        );
+
         Ok(())
     }
 
@@ -143,7 +168,13 @@ impl Project {
         all_projects: &mut Vec<Project>,
         id: &String,
         name: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_project: Option<Project> = None;
 
         if !String::from(name)
@@ -173,6 +204,8 @@ impl Project {
             return Err(String::from("Error: Project not found"));
         }
 
+        AuditEntry::record(audit, actor, "update", "projects", &format!("{} name", id));
+
         Ok(())
     }
 
@@ -180,7 +213,13 @@ impl Project {
         all_projects: &mut Vec<Project>,
         id: &String,
         description: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_project: Option<Project> = None;
 
         if description.trim().len() > 0 && String::from(description).chars().any(|c| c == ';') {
@@ -207,6 +246,14 @@ impl Project {
             return Err(String::from("Error: Project not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "update",
+            "projects",
+            &format!("{} description", id),
+        );
+
         Ok(())
     }
 
@@ -214,7 +261,13 @@ impl Project {
         all_projects: &mut Vec<Project>,
         id: &String,
         api_path: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_project: Option<Project> = None;
 
         for project in all_projects.iter() {
@@ -258,10 +311,27 @@ impl Project {
             return Err(String::from("Error: Project not found"));
         }
 
+        AuditEntry::record(
+            audit,
+            actor,
+            "update",
+            "projects",
+            &format!("{} api_path", id),
+        );
+
         Ok(())
     }
 
-    pub fn delete(all_projects: &mut Vec<Project>, id: &String) -> Result<(), String> {
+    pub fn delete(
+        all_projects: &mut Vec<Project>,
+        id: &String,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_project: Option<Project> = None;
 
         for project in all_projects.iter_mut() {
@@ -288,6 +358,8 @@ impl Project {
 
         *all_projects = updated_projects;
 
+        AuditEntry::record(audit, actor, "delete", "projects", id);
+
         Ok(())
     }
 
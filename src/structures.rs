@@ -1,6 +1,7 @@
 // use crate::encryption::EncryptionKey;
+use regex::Regex;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     TEXT,
     EMAIL,
@@ -13,6 +14,8 @@ pub enum Type {
     BOOLEAN,
     UID,
     JSON,
+    TIMESTAMPTZ,
+    DURATION,
     CUSTOM(String),
 }
 
@@ -22,6 +25,34 @@ impl Default for Type {
     }
 }
 
+impl Type {
+    pub fn from_string(stype_txt: &str) -> Type {
+        match stype_txt {
+            "text" => Type::TEXT,
+            "email" => Type::EMAIL,
+            "password" => Type::PASSWORD,
+            "richtext" => Type::RICHTEXT,
+            "number" => Type::NUMBER,
+            "enum" => Type::ENUM,
+            "date" => Type::DATE,
+            "media" => Type::MEDIA,
+            "bool" => Type::BOOLEAN,
+            "uid" => Type::UID,
+            "json" => Type::JSON,
+            "timestamptz" => Type::TIMESTAMPTZ,
+            "duration" => Type::DURATION,
+            _ => Type::CUSTOM(String::from(stype_txt)),
+        }
+    }
+
+    pub fn is_numeric(self: &Self) -> bool {
+        match self {
+            Type::NUMBER => true,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct Structure {
     pub id: String,
@@ -30,6 +61,8 @@ pub struct Structure {
     default_val: String,
     min: usize,
     max: usize,
+    min_length: usize,
+    max_length: usize,
     encrypted: bool,
     unique: bool,
     regex_pattern: String,
@@ -45,6 +78,8 @@ impl Structure {
         default_val: &str,
         min: usize,
         max: usize,
+        min_length: usize,
+        max_length: usize,
         encrypted: bool,
         unique: bool,
         regex_pattern: &str,
@@ -60,6 +95,8 @@ impl Structure {
         //         default_val,
         //         min,
         //         max,
+        //         min_length,
+        //         max_length,
         //         encrypted,
         //         unique,
         //         regex_pattern,
@@ -79,6 +116,8 @@ impl Structure {
             default_val: "".to_string(),
             min: 0,
             max: 0,
+            min_length: 0,
+            max_length: 0,
             encrypted: false,
             unique: false,
             regex_pattern: "".to_string(),
@@ -139,6 +178,24 @@ impl Structure {
             }
         }
 
+        if !has_error {
+            let min_length_update = Self::update_min_length(all_structures, &new_id, min_length);
+            if let Err(e) = min_length_update {
+                has_error = true;
+                println!("Error: {}", e);
+                latest_error = e;
+            }
+        }
+
+        if !has_error {
+            let max_length_update = Self::update_max_length(all_structures, &new_id, max_length);
+            if let Err(e) = max_length_update {
+                has_error = true;
+                println!("Error: {}", e);
+                latest_error = e;
+            }
+        }
+
         if !has_error {
             let encrypted_update = Self::update_encrypted(all_structures, &new_id, encrypted);
             if let Err(e) = encrypted_update {
@@ -295,23 +352,18 @@ impl Structure {
             ));
         }
 
-        let stype = match stype_txt {
-            "text" => Type::TEXT,
-            "email" => Type::EMAIL,
-            "password" => Type::PASSWORD,
-            "richtext" => Type::RICHTEXT,
-            "number" => Type::NUMBER,
-            "enum" => Type::ENUM,
-            "date" => Type::DATE,
-            "media" => Type::MEDIA,
-            "bool" => Type::BOOLEAN,
-            "uid" => Type::UID,
-            "json" => Type::JSON,
-            _ => Type::CUSTOM(String::from(stype_txt)),
-        };
+        let stype = Type::from_string(stype_txt);
 
         for structure in all_structures.iter_mut() {
             if structure.id == *id {
+                if let Err(e) = validate_default(&stype, &structure.default_val) {
+                    return Err(e);
+                }
+                if stype.is_numeric() && (structure.min_length > 0 || structure.max_length > 0) {
+                    return Err(String::from(
+                        "Error: length limits do not apply to numeric types",
+                    ));
+                }
                 found_structure = Some(structure.clone());
                 structure.stype = stype;
                 break;
@@ -343,6 +395,9 @@ impl Structure {
 
         for structure in all_structures.iter_mut() {
             if structure.id == *id {
+                if let Err(e) = validate_default(&structure.stype, default_val.trim()) {
+                    return Err(e);
+                }
                 found_structure = Some(structure.clone());
                 structure.default_val = String::from(default_val.trim());
                 break;
@@ -400,6 +455,64 @@ impl Structure {
         Ok(())
     }
 
+    pub fn update_min_length(
+        all_structures: &mut Vec<Structure>,
+        id: &String,
+        min_length: usize,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                if min_length > 0 && structure.stype.is_numeric() {
+                    return Err(String::from(
+                        "Error: length limits do not apply to numeric types",
+                    ));
+                } else if structure.max_length > 0 && min_length > structure.max_length {
+                    return Err(String::from("Error: min_length is greater than max_length"));
+                }
+                found_structure = Some(structure.clone());
+                structure.min_length = min_length;
+                break;
+            }
+        }
+
+        if let None = found_structure {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
+    pub fn update_max_length(
+        all_structures: &mut Vec<Structure>,
+        id: &String,
+        max_length: usize,
+    ) -> Result<(), String> {
+        let mut found_structure: Option<Structure> = None;
+
+        for structure in all_structures.iter_mut() {
+            if structure.id == *id {
+                if max_length > 0 && structure.stype.is_numeric() {
+                    return Err(String::from(
+                        "Error: length limits do not apply to numeric types",
+                    ));
+                } else if max_length > 0 && max_length < structure.min_length {
+                    return Err(String::from("Error: min_length is greater than max_length"));
+                }
+                found_structure = Some(structure.clone());
+                structure.max_length = max_length;
+                break;
+            }
+        }
+
+        if let None = found_structure {
+            return Err(String::from("Error: Structure not found"));
+        }
+
+        Ok(())
+    }
+
     pub fn update_encrypted(
         all_structures: &mut Vec<Structure>,
         id: &String,
@@ -521,6 +634,8 @@ impl Structure {
                 default_val: structure.default_val.clone(),
                 min: structure.min.clone(),
                 max: structure.max.clone(),
+                min_length: structure.min_length.clone(),
+                max_length: structure.max_length.clone(),
                 encrypted: structure.encrypted.clone(),
                 unique: structure.unique.clone(),
                 regex_pattern: structure.regex_pattern.clone(),
@@ -533,6 +648,10 @@ impl Structure {
         Ok(())
     }
 
+    pub fn get_type(self: &Self) -> Type {
+        self.stype.clone()
+    }
+
     pub fn stringify(all_structures: &Vec<Structure>) -> String {
         let mut stringified_structures = String::new();
 
@@ -556,13 +675,51 @@ impl Structure {
         let current_structure = structure_str.split("|").collect::<Vec<&str>>();
         let mut tmp_structures = Vec::<Structure>::new();
 
-        if try_add_structure(&current_structure, &mut tmp_structures) {
+        if try_add_structure(&current_structure, &mut tmp_structures) && tmp_structures.len() > 0 {
             return Ok(tmp_structures[0].clone());
         }
 
         Err(String::from("Error: Wrong format for Structure data"))
     }
 
+    pub fn to_sql(structure: Structure) -> String {
+        // Arrays have no portable column type, and encrypted values are stored
+        // as ciphertext that outgrows any plaintext length limit
+        let sql_type = if structure.array || structure.encrypted {
+            "TEXT".to_string()
+        } else {
+            match structure.stype.clone() {
+                Type::NUMBER => "NUMERIC".to_string(),
+                Type::BOOLEAN => "BOOLEAN".to_string(),
+                Type::DATE => "DATE".to_string(),
+                Type::TIMESTAMPTZ => "TIMESTAMP WITH TIME ZONE".to_string(),
+                Type::DURATION => "INTERVAL".to_string(),
+                Type::JSON => "JSON".to_string(),
+                Type::TEXT | Type::EMAIL | Type::PASSWORD | Type::UID
+                    if structure.max_length > 0 =>
+                {
+                    format!("VARCHAR({})", structure.max_length)
+                }
+                Type::TEXT
+                | Type::EMAIL
+                | Type::PASSWORD
+                | Type::UID
+                | Type::RICHTEXT
+                | Type::ENUM
+                | Type::MEDIA => "TEXT".to_string(),
+                // Custom structures are nested records, kept as serialized text
+                Type::CUSTOM(_) => "TEXT".to_string(),
+            }
+        };
+
+        format!(
+            "\"{}\" {}{}",
+            structure.id,
+            sql_type,
+            if structure.unique { " UNIQUE" } else { "" }
+        )
+    }
+
     pub fn to_string(structure: Structure) -> String {
         let stype_txt = match structure.stype.clone() {
             Type::TEXT => "text".to_string(),
@@ -576,11 +733,13 @@ impl Structure {
             Type::BOOLEAN => "bool".to_string(),
             Type::UID => "uid".to_string(),
             Type::JSON => "json".to_string(),
+            Type::TIMESTAMPTZ => "timestamptz".to_string(),
+            Type::DURATION => "duration".to_string(),
             Type::CUSTOM(txt) => txt.clone(),
         };
 
         format!(
-            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}",
             structure.id,
             structure.name,
             stype_txt,
@@ -590,7 +749,9 @@ impl Structure {
             structure.encrypted,
             structure.unique,
             structure.regex_pattern,
-            structure.array
+            structure.array,
+            structure.min_length,
+            structure.max_length
         )
     }
 }
@@ -627,14 +788,44 @@ pub fn try_add_structure(array: &Vec<&str>, final_structures: &mut Vec<Structure
         _ => false,
     };
 
+    let mut min = min.unwrap();
+    let mut max = max.unwrap();
+    let mut min_length: usize = 0;
+    let mut max_length: usize = 0;
+
+    if array.len() >= 12 {
+        let parsed_min_length = array[10].parse::<usize>();
+        if let Err(e) = parsed_min_length {
+            println!("{}", e);
+            return false;
+        }
+
+        let parsed_max_length = array[11].parse::<usize>();
+        if let Err(e) = parsed_max_length {
+            println!("{}", e);
+            return false;
+        }
+
+        min_length = parsed_min_length.unwrap();
+        max_length = parsed_max_length.unwrap();
+    } else if !Type::from_string(array[2]).is_numeric() {
+        // Older data stored length limits in min/max for non-numeric types
+        min_length = min;
+        max_length = max;
+        min = 0;
+        max = 0;
+    }
+
     let create_structure = Structure::create(
         final_structures,
         array[0],
         array[1],
         array[2],
         array[3],
-        min.unwrap(),
-        max.unwrap(),
+        min,
+        max,
+        min_length,
+        max_length,
         encrypted,
         unique,
         array[8],
@@ -647,3 +838,196 @@ pub fn try_add_structure(array: &Vec<&str>, final_structures: &mut Vec<Structure
 
     true
 }
+
+fn validate_default(stype: &Type, default_val: &str) -> Result<(), String> {
+    if default_val.is_empty() {
+        return Ok(());
+    }
+
+    match stype {
+        Type::TIMESTAMPTZ => match parse_timestamptz(default_val) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(String::from(
+                "Error: default_val is not a valid timestamptz",
+            )),
+        },
+        Type::DURATION => match parse_duration(default_val) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(String::from("Error: default_val is not a valid duration")),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Parses an RFC 3339 timestamp such as `2021-06-01T14:30:00+02:00` into
+/// UNIX seconds in UTC and the offset it was written with, in seconds.
+/// Comparing timestamps should only ever use the UTC value.
+pub fn parse_timestamptz(timestamp: &str) -> Result<(i64, i32), String> {
+    let timestamp_regex = Regex::new(
+        r"^(\d{4})-(\d{2})-(\d{2})[T ](\d{2}):(\d{2}):(\d{2})(Z|([+-])(\d{2}):(\d{2}))$",
+    )
+    .unwrap();
+
+    let captures = match timestamp_regex.captures(timestamp.trim()) {
+        Some(captures) => captures,
+        None => return Err(String::from("Error: timestamptz has an invalid format")),
+    };
+
+    let number = |i: usize| -> i64 {
+        match captures.get(i) {
+            Some(m) => m.as_str().parse::<i64>().unwrap_or(0),
+            None => 0,
+        }
+    };
+
+    let (year, month, day) = (number(1), number(2), number(3));
+    let (hour, minute, second) = (number(4), number(5), number(6));
+    let (offset_hour, offset_minute) = (number(9), number(10));
+
+    if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month) {
+        return Err(String::from("Error: timestamptz has an invalid date"));
+    } else if hour > 23 || minute > 59 || second > 59 {
+        return Err(String::from("Error: timestamptz has an invalid time"));
+    } else if offset_hour > 14 || offset_minute > 59 {
+        return Err(String::from("Error: timestamptz has an invalid offset"));
+    }
+
+    let mut offset = offset_hour * 3600 + offset_minute * 60;
+    if let Some(sign) = captures.get(8) {
+        if sign.as_str() == "-" {
+            offset = -offset;
+        }
+    }
+
+    let local = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second;
+
+    Ok((local - offset, offset as i32))
+}
+
+/// Formats UTC seconds back into RFC 3339 in the given offset, the inverse
+/// of `parse_timestamptz`.
+pub fn format_timestamptz(utc: i64, offset: i32) -> String {
+    let local = utc + offset as i64;
+    let (year, month, day) = civil_from_days(local.div_euclid(86400));
+    let seconds = local.rem_euclid(86400);
+
+    let offset_txt = if offset == 0 {
+        String::from("Z")
+    } else {
+        format!(
+            "{}{:02}:{:02}",
+            if offset < 0 { "-" } else { "+" },
+            offset.abs() / 3600,
+            offset.abs() % 3600 / 60
+        )
+    };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+        offset_txt
+    )
+}
+
+/// Parses a duration such as `2h30m` or `1w2d` into seconds. Units are
+/// w, d, h, m and s, and each may appear at most once, largest first.
+pub fn parse_duration(duration: &str) -> Result<u64, String> {
+    let duration_regex = Regex::new(r"^(\d+w)?(\d+d)?(\d+h)?(\d+m)?(\d+s)?$").unwrap();
+
+    let duration = duration.trim();
+    let captures = match duration_regex.captures(duration) {
+        Some(captures) if duration.len() > 0 => captures,
+        _ => return Err(String::from("Error: duration has an invalid format")),
+    };
+
+    let units: [u64; 5] = [604800, 86400, 3600, 60, 1];
+    let mut total: u64 = 0;
+
+    for (i, unit) in units.iter().enumerate() {
+        if let Some(m) = captures.get(i + 1) {
+            let txt = m.as_str();
+            let value = txt[..txt.len() - 1].parse::<u64>();
+            let seconds = match value {
+                Ok(value) => value.checked_mul(*unit),
+                Err(_) => None,
+            };
+
+            total = match seconds.and_then(|seconds| total.checked_add(seconds)) {
+                Some(total) => total,
+                None => return Err(String::from("Error: duration is too long")),
+            };
+        }
+    }
+
+    Ok(total)
+}
+
+/// Formats seconds in the same notation `parse_duration` reads.
+pub fn format_duration(seconds: u64) -> String {
+    if seconds == 0 {
+        return String::from("0s");
+    }
+
+    let units: [(u64, &str); 5] = [
+        (604800, "w"),
+        (86400, "d"),
+        (3600, "h"),
+        (60, "m"),
+        (1, "s"),
+    ];
+    let mut remaining = seconds;
+    let mut formatted = String::new();
+
+    for (unit, suffix) in units.iter() {
+        if remaining >= *unit {
+            formatted = format!("{}{}{}", formatted, remaining / unit, suffix);
+            remaining %= unit;
+        }
+    }
+
+    formatted
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
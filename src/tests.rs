@@ -1,14 +1,17 @@
 #![allow(unused_assignments)]
 #[cfg(test)]
 use crate::{
-    collection::{fetch_all_collections, save_all_collections, Collection},
+    audit::{fetch_all_audit_entries, save_all_audit_entries, Action, AuditEntry},
+    collection::{export_sql, fetch_all_collections, save_all_collections, Collection},
     config::{fetch_all_configs, save_all_configs, Config},
     custom_structures::CustomStructure,
     encryption::{fetch_encryption_key, save_encryption_key, EncryptionKey},
-    io::remove_file,
+    io::{fetch_file, remove_file, save_file},
     mappings::{fetch_all_mappings, save_all_mappings, Mapping},
     project::{fetch_all_projects, save_all_projects, Project},
-    structures::Structure,
+    structures::{
+        format_duration, format_timestamptz, parse_duration, parse_timestamptz, Structure, Type,
+    },
     user::{fetch_all_users, save_all_users, User},
 };
 
@@ -56,6 +59,7 @@ fn test_mappings() {
 
 #[test]
 fn test_users() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
     let file_name: &str = "data/users_test.txt";
     remove_file(file_name.to_string());
 
@@ -70,6 +74,8 @@ fn test_users() {
         "test@test.com",
         "Test123*",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(test_user, Ok(()));
 
@@ -81,6 +87,8 @@ fn test_users() {
         "test@test2.com",
         "Test123*",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -97,6 +105,8 @@ fn test_users() {
         "test@test2.com",
         "Test123*",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -113,6 +123,8 @@ fn test_users() {
         "test@test2.com",
         "Test123*",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -127,6 +139,8 @@ fn test_users() {
         "test@test.com",
         "Test123*",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(test_user2, Err(String::from("Error: email already taken")));
 
@@ -138,6 +152,8 @@ fn test_users() {
         "test@@test2.teeeeeeeeest",
         "Test123*",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -152,6 +168,8 @@ fn test_users() {
         "test@test2..teeeeeeeeest",
         "Test123*",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -166,6 +184,8 @@ fn test_users() {
         "test@test2.com",
         "Test",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -182,6 +202,8 @@ fn test_users() {
         "test@test2.com",
         "testtest",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -198,6 +220,8 @@ fn test_users() {
         "test@test2.com",
         "TESTTEST",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -214,6 +238,8 @@ fn test_users() {
         "test@test2.com",
         "testTEST",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -230,6 +256,8 @@ fn test_users() {
         "test@test2.com",
         "Test123;",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_user2,
@@ -246,25 +274,58 @@ fn test_users() {
         "test@test2.com",
         "Test123*&^()[]{}*-_",
         0,
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(test_user2, Ok(()));
 
     let login_test_user2 = User::login(&all_users, "te_st", "Test123*&^()[]{}*-_");
 
     if let Ok(successful_login) = login_test_user2 {
-        let test_user2 = User::update_name(&mut all_users, &successful_login.id, "Test", "Tester");
+        let test_user2 = User::update_name(
+            &mut all_users,
+            &successful_login.id,
+            "Test",
+            "Tester",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(test_user2, Ok(()));
 
-        let test_user2 = User::update_username(&mut all_users, &successful_login.id, "test2");
+        let test_user2 = User::update_username(
+            &mut all_users,
+            &successful_login.id,
+            "test2",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(test_user2, Ok(()));
 
-        let test_user2 = User::update_email(&mut all_users, &successful_login.id, "test2@test.com");
+        let test_user2 = User::update_email(
+            &mut all_users,
+            &successful_login.id,
+            "test2@test.com",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(test_user2, Ok(()));
 
-        let test_user2 = User::update_password(&mut all_users, &successful_login.id, "Test123*");
+        let test_user2 = User::update_password(
+            &mut all_users,
+            &successful_login.id,
+            "Test123*",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(test_user2, Ok(()));
 
-        let test_user2 = User::update_role(&mut all_users, &successful_login.id, 2);
+        let test_user2 = User::update_role(
+            &mut all_users,
+            &successful_login.id,
+            2,
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(test_user2, Ok(()));
     };
 
@@ -273,6 +334,7 @@ fn test_users() {
 
 #[test]
 fn test_projects() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
     let file_name: &str = "data/projects_test.txt";
     remove_file(file_name.to_string());
 
@@ -285,6 +347,8 @@ fn test_projects() {
         "Test Project",
         "This is a test project.",
         "/api/v1/projects",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(test_project, Ok(()));
 
@@ -294,6 +358,8 @@ fn test_projects() {
         "Test Project",
         "This is a test project.",
         "/api/v1/projects",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_project2,
@@ -306,6 +372,8 @@ fn test_projects() {
         "Test *** Project",
         "This is a test project.",
         "/api/v1/projects",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_project2,
@@ -318,6 +386,8 @@ fn test_projects() {
         "Test Project",
         "This is a test project.",
         "/api/v1/projects-",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_project2,
@@ -332,6 +402,8 @@ fn test_projects() {
         "Test Project",
         "This is a test project.",
         "/api/v1/Projects",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_project2,
@@ -346,6 +418,8 @@ fn test_projects() {
         "Test Project",
         "This is a test project.",
         "/api/v1/projects",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_project2,
@@ -358,6 +432,8 @@ fn test_projects() {
         "Test Project",
         "This is a test project.",
         "/api/v1/projects",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_project2,
@@ -370,6 +446,8 @@ fn test_projects() {
         "Test Project",
         "This is a test project;",
         "/api/v1/projects2",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(
         test_project2,
@@ -384,25 +462,47 @@ fn test_projects() {
         "Test Project",
         "This is a new test project.",
         "/api/v1/projects2",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(test_project2, Ok(()));
 
     let test2_id = String::from("test2");
 
-    let test_project3 = Project::update_name(&mut all_projects, &test2_id, "Test Project 3");
+    let test_project3 = Project::update_name(
+        &mut all_projects,
+        &test2_id,
+        "Test Project 3",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(test_project3, Ok(()));
 
     let test_project3 = Project::update_description(
         &mut all_projects,
         &test2_id,
         "This is a new test project (3).",
+        Some(&mut all_audit_entries),
+        "test",
     );
     assert_eq!(test_project3, Ok(()));
 
-    let test_project3 = Project::update_api_path(&mut all_projects, &test2_id, "/api/v1/projects3");
+    let test_project3 = Project::update_api_path(
+        &mut all_projects,
+        &test2_id,
+        "/api/v1/projects3",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(test_project3, Ok(()));
 
-    let test_project3 = Project::update_id(&mut all_projects, &test2_id, "test3");
+    let test_project3 = Project::update_id(
+        &mut all_projects,
+        &test2_id,
+        "test3",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(test_project3, Ok(()));
 
     save_all_projects(&all_projects, String::from(file_name), &String::new());
@@ -410,22 +510,41 @@ fn test_projects() {
 
 #[test]
 fn test_configs() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
     let file_name: &str = "data/configs_test.txt";
     remove_file(file_name.to_string());
 
     let mut all_configs = fetch_all_configs(file_name.to_string(), &String::new());
     println!("{:#?}", all_configs);
 
-    let test_config = Config::create(&mut all_configs, "TEST", "test");
+    let test_config = Config::create(
+        &mut all_configs,
+        "TEST",
+        "test",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(test_config, Ok(()));
 
-    let test_config2 = Config::create(&mut all_configs, "test?", "Test2");
+    let test_config2 = Config::create(
+        &mut all_configs,
+        "test?",
+        "Test2",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(
         test_config2,
         Err(String::from("Error: name contains an invalid character"))
     );
 
-    let test_config2 = Config::create(&mut all_configs, "test", "Test2");
+    let test_config2 = Config::create(
+        &mut all_configs,
+        "test",
+        "Test2",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(
         test_config2,
         Err(String::from(
@@ -433,23 +552,245 @@ fn test_configs() {
         ))
     );
 
-    let test_config2 = Config::create(&mut all_configs, "test2", "Test2|");
+    let test_config2 = Config::create(
+        &mut all_configs,
+        "test2",
+        "Test2|",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(
         test_config2,
         Err(String::from("Error: value contains an invalid character"))
     );
 
-    let test_config2 = Config::create(&mut all_configs, "test2", "Test2");
+    let test_config2 = Config::create(
+        &mut all_configs,
+        "test2",
+        "Test2",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(test_config2, Ok(()));
 
     let test2_id = "test2";
 
-    let test_config2 = Config::update_value(&mut all_configs, test2_id, "TEST2VAL");
+    let test_config2 = Config::update_value(
+        &mut all_configs,
+        test2_id,
+        "TEST2VAL",
+        Some(&mut all_audit_entries),
+        "test",
+    );
     assert_eq!(test_config2, Ok(()));
 
     save_all_configs(&all_configs, String::from(file_name), &String::new());
 }
 
+#[test]
+fn test_audit() {
+    let file_name: &str = "data/audit_test.txt";
+    remove_file(file_name.to_string());
+
+    let mut all_entries = fetch_all_audit_entries(file_name.to_string(), &String::new());
+    println!("{:#?}", all_entries);
+
+    let test_entry = AuditEntry::create(&mut all_entries, "test", "create", "users", "test");
+    assert_eq!(test_entry, Ok(()));
+
+    let test_entry = AuditEntry::create(&mut all_entries, "test", "schema", "collections", "posts");
+    assert_eq!(test_entry, Ok(()));
+
+    let test_entry2 = AuditEntry::create(&mut all_entries, "test", "truncate", "users", "");
+    assert_eq!(test_entry2, Err(String::from("Error: action is invalid")));
+
+    let test_entry2 = AuditEntry::create(&mut all_entries, "test", "delete", "users;", "");
+    assert_eq!(
+        test_entry2,
+        Err(String::from("Error: target contains an invalid character"))
+    );
+
+    let test_entry2 = AuditEntry::create(&mut all_entries, "test", "update", "users", "a\nb");
+    assert_eq!(
+        test_entry2,
+        Err(String::from("Error: details contains an invalid character"))
+    );
+
+    let test_entry2 = AuditEntry::create(&mut all_entries, "test\r", "update", "users", "");
+    assert_eq!(
+        test_entry2,
+        Err(String::from("Error: user_id contains an invalid character"))
+    );
+
+    let test_entry2 = AuditEntry::create(&mut all_entries, "", "update", "users", "");
+    assert_eq!(
+        test_entry2,
+        Err(String::from(
+            "Error: user_id does not contain enough characters"
+        ))
+    );
+
+    let test_entry2 = AuditEntry::from_string("broken;entry");
+    assert_eq!(
+        test_entry2.map(|entry| entry.get_target()),
+        Err(String::from("Error: Wrong format for Audit Entry data"))
+    );
+
+    let test_entry2 = AuditEntry::from_string("id;u;notanumber;update;users;x");
+    assert_eq!(
+        test_entry2.map(|entry| entry.get_target()),
+        Err(String::from(
+            "Error when parsing timestamp: invalid digit found in string"
+        ))
+    );
+
+    let test_entry2 = AuditEntry::from_string("id;u;10;bogus;users;x");
+    assert_eq!(
+        test_entry2.map(|entry| entry.get_target()),
+        Err(String::from("Error: action is invalid"))
+    );
+
+    let test_entry2 = AuditEntry::create(&mut all_entries, "test2", "delete", "users", "test");
+    assert_eq!(test_entry2, Ok(()));
+
+    let user_entries = AuditEntry::query(&all_entries, "test", "", 0);
+    assert_eq!(user_entries.len(), 2);
+
+    let target_entries = AuditEntry::query(&all_entries, "", "users", 0);
+    assert_eq!(target_entries.len(), 2);
+    assert_eq!(target_entries[1].get_action(), Action::DELETE);
+
+    AuditEntry::apply_retention(&mut all_entries, 2, 0);
+    assert_eq!(all_entries.len(), 2);
+    assert_eq!(all_entries[0].get_target(), "collections");
+
+    save_all_audit_entries(&all_entries, String::from(file_name), &String::new());
+
+    save_file(
+        String::from(file_name),
+        format!(
+            "{}\nnot an entry",
+            fetch_file(file_name.to_string(), &String::new())
+        ),
+        &String::new(),
+    );
+
+    let fetched_entries = fetch_all_audit_entries(file_name.to_string(), &String::new());
+    assert_eq!(fetched_entries.len(), 2);
+    assert_eq!(fetched_entries[1].get_action(), Action::DELETE);
+}
+
+#[test]
+fn test_audited_mutations() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
+    let mut all_users = Vec::<User>::new();
+
+    let test_user = User::create(
+        &mut all_users,
+        "Test",
+        "Tester",
+        "test",
+        "test@test.com",
+        "Test123*",
+        0,
+        Some(&mut all_audit_entries),
+        "admin",
+    );
+    assert_eq!(test_user, Ok(()));
+    assert_eq!(all_audit_entries.len(), 1);
+    assert_eq!(all_audit_entries[0].get_action(), Action::CREATE);
+
+    let test_user2 = User::create(
+        &mut all_users,
+        "Test",
+        "Tester",
+        "test",
+        "test@test2.com",
+        "Test123*",
+        0,
+        Some(&mut all_audit_entries),
+        "admin",
+    );
+    assert_eq!(
+        test_user2,
+        Err(String::from("Error: username already taken"))
+    );
+    assert_eq!(all_audit_entries.len(), 1);
+
+    let test_user3 = User::create(
+        &mut all_users,
+        "Test",
+        "Tester",
+        "test3",
+        "test@test3.com",
+        "Test123*",
+        0,
+        Some(&mut all_audit_entries),
+        "evil;actor",
+    );
+    assert_eq!(
+        test_user3,
+        Err(String::from("Error: actor contains an invalid character"))
+    );
+
+    let test_user3 = User::create(
+        &mut all_users,
+        "Test",
+        "Tester",
+        "test3",
+        "test@test3.com",
+        "Test123*",
+        0,
+        Some(&mut all_audit_entries),
+        " ",
+    );
+    assert_eq!(
+        test_user3,
+        Err(String::from(
+            "Error: actor does not contain enough characters"
+        ))
+    );
+    assert_eq!(all_users.len(), 1);
+    assert_eq!(all_audit_entries.len(), 1);
+
+    let user_id = all_users[0].id.clone();
+    let test_user = User::update_email(
+        &mut all_users,
+        &user_id,
+        "test@test3.com",
+        Some(&mut all_audit_entries),
+        "admin",
+    );
+    assert_eq!(test_user, Ok(()));
+
+    let test_user = User::delete(
+        &mut all_users,
+        &user_id,
+        Some(&mut all_audit_entries),
+        "admin",
+    );
+    assert_eq!(test_user, Ok(()));
+
+    let mut all_configs = Vec::<Config>::new();
+    Config::create(
+        &mut all_configs,
+        "TEST",
+        "test",
+        Some(&mut all_audit_entries),
+        "root",
+    )
+    .unwrap();
+
+    let admin_entries = AuditEntry::query(&all_audit_entries, "admin", "users", 0);
+    assert_eq!(admin_entries.len(), 3);
+    assert_eq!(admin_entries[1].get_action(), Action::UPDATE);
+    assert_eq!(admin_entries[2].get_action(), Action::DELETE);
+
+    let root_entries = AuditEntry::query(&all_audit_entries, "root", "", 0);
+    assert_eq!(root_entries.len(), 1);
+    assert_eq!(root_entries[0].get_target(), "configs");
+}
+
 #[test]
 fn test_encryption() {
     let file_name: &str = "data/encryption_key_test.txt";
@@ -480,6 +821,7 @@ fn test_encryption() {
 
 #[test]
 fn test_correct_collection() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
     let file_name: &str = "data/collection_ok_test.txt";
     remove_file(file_name.to_string());
 
@@ -493,6 +835,8 @@ fn test_correct_collection() {
             "konnect",
             "Posts",
             "To store blog posts.",
+            Some(&mut all_audit_entries),
+            "test",
         );
         if let Err(e) = create_collection {
             println!("{}", e);
@@ -505,6 +849,8 @@ fn test_correct_collection() {
             "Title",
             "text",
             "test title",
+            0,
+            0,
             5,
             20,
             false,
@@ -520,6 +866,8 @@ fn test_correct_collection() {
             "media",
             "https://test.image.com",
             0,
+            0,
+            0,
             200,
             false,
             false,
@@ -533,6 +881,8 @@ fn test_correct_collection() {
             "Content",
             "richtext",
             "[ Content goes here ]",
+            0,
+            0,
             30,
             2000,
             false,
@@ -549,6 +899,8 @@ fn test_correct_collection() {
             "0",
             0,
             9999,
+            0,
+            0,
             false,
             false,
             "",
@@ -562,6 +914,8 @@ fn test_correct_collection() {
             "comment",
             "0",
             0,
+            0,
+            0,
             9999,
             false,
             false,
@@ -576,6 +930,8 @@ fn test_correct_collection() {
             "boolean",
             "false",
             0,
+            0,
+            0,
             5,
             false,
             false,
@@ -583,8 +939,14 @@ fn test_correct_collection() {
             true,
         )
         .unwrap();
-        Collection::set_structures(&mut all_collections, &"posts".to_string(), all_structures)
-            .unwrap();
+        Collection::set_structures(
+            &mut all_collections,
+            &"posts".to_string(),
+            all_structures,
+            Some(&mut all_audit_entries),
+            "test",
+        )
+        .unwrap();
 
         let mut all_custom_structures = Vec::<CustomStructure>::new();
         let mut tmp_structures = Vec::<Structure>::new();
@@ -595,6 +957,8 @@ fn test_correct_collection() {
             "UID",
             "uid",
             "",
+            0,
+            0,
             5,
             20,
             false,
@@ -609,6 +973,8 @@ fn test_correct_collection() {
             "Value",
             "text",
             "",
+            0,
+            0,
             1,
             100,
             false,
@@ -629,14 +995,463 @@ fn test_correct_collection() {
             &mut all_collections,
             &"posts".to_string(),
             all_custom_structures,
+            Some(&mut all_audit_entries),
+            "test",
         )
         .unwrap();
     }
     save_all_collections(&all_collections, file_name.to_string(), &String::new());
 }
 
+#[test]
+fn test_clone_collection() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
+    let mut all_collections = Vec::<Collection>::new();
+    Collection::create(
+        &mut all_collections,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+        Some(&mut all_audit_entries),
+        "test",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "test title",
+        0,
+        0,
+        5,
+        20,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &"posts".to_string(),
+        all_structures,
+        Some(&mut all_audit_entries),
+        "test",
+    )
+    .unwrap();
+
+    let test_clone = Collection::clone_collection(
+        &mut all_collections,
+        &"posts".to_string(),
+        "archive",
+        Some(&mut all_audit_entries),
+        "test",
+    );
+    assert_eq!(test_clone, Ok(()));
+    assert_eq!(all_collections.len(), 2);
+    assert_eq!(
+        Collection::to_string(all_collections[1].clone()),
+        Collection::to_string(all_collections[0].clone()).replacen("posts", "archive", 1)
+    );
+
+    let test_clone = Collection::clone_collection(
+        &mut all_collections,
+        &"posts".to_string(),
+        "archive",
+        Some(&mut all_audit_entries),
+        "test",
+    );
+    assert_eq!(test_clone, Err(String::from("Error: id is already in use")));
+    assert_eq!(all_collections.len(), 2);
+
+    let test_clone = Collection::clone_collection(
+        &mut all_collections,
+        &"drafts".to_string(),
+        "archive2",
+        Some(&mut all_audit_entries),
+        "test",
+    );
+    assert_eq!(test_clone, Err(String::from("Error: Collection not found")));
+}
+
+#[test]
+fn test_export_sql() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
+    let file_name: &str = "data/export_test.sql";
+    remove_file(file_name.to_string());
+
+    let mut all_collections = Vec::<Collection>::new();
+    Collection::create(
+        &mut all_collections,
+        "posts",
+        "konnect",
+        "Posts",
+        "To store blog posts.",
+        Some(&mut all_audit_entries),
+        "test",
+    )
+    .unwrap();
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "title",
+        "Title",
+        "text",
+        "test title",
+        0,
+        0,
+        5,
+        20,
+        false,
+        true,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "views",
+        "Views",
+        "number",
+        "0",
+        0,
+        9999,
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "secret",
+        "Secret",
+        "password",
+        "",
+        0,
+        0,
+        0,
+        50,
+        true,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+    Structure::create(
+        &mut all_structures,
+        "comment",
+        "Comments",
+        "comment",
+        "0",
+        0,
+        0,
+        0,
+        9999,
+        false,
+        false,
+        "",
+        true,
+    )
+    .unwrap();
+    Collection::set_structures(
+        &mut all_collections,
+        &"posts".to_string(),
+        all_structures,
+        Some(&mut all_audit_entries),
+        "test",
+    )
+    .unwrap();
+
+    let expected_sql = "CREATE TABLE \"posts\" (\n  \"title\" VARCHAR(20) UNIQUE,\n  \"views\" NUMERIC,\n  \"secret\" TEXT,\n  \"comment\" TEXT\n);";
+    assert_eq!(
+        Collection::to_sql(all_collections[0].clone()),
+        Ok(String::from(expected_sql))
+    );
+
+    Collection::create(
+        &mut all_collections,
+        "drafts",
+        "konnect",
+        "Drafts",
+        "To store drafts.",
+        Some(&mut all_audit_entries),
+        "test",
+    )
+    .unwrap();
+    assert_eq!(
+        Collection::to_sql(all_collections[1].clone()),
+        Err(String::from("Error: Collection has no structures"))
+    );
+
+    export_sql(&all_collections, file_name.to_string());
+    assert_eq!(
+        fetch_file(file_name.to_string(), &String::new()),
+        expected_sql
+    );
+}
+
+#[test]
+fn test_structure_lengths() {
+    let legacy_text = Structure::from_string("title|Title|text|test title|5|20|false|false||false");
+    assert_eq!(
+        Structure::to_string(legacy_text.unwrap()),
+        "title|Title|text|test title|0|0|false|false||false|5|20"
+    );
+
+    let legacy_number = Structure::from_string("views|Views|number|0|0|9999|false|false||false");
+    assert_eq!(
+        Structure::to_string(legacy_number.unwrap()),
+        "views|Views|number|0|0|9999|false|false||false|0|0"
+    );
+
+    let mut all_structures = Vec::<Structure>::new();
+    let test_structure = Structure::create(
+        &mut all_structures,
+        "slug",
+        "Slug",
+        "text",
+        "",
+        0,
+        0,
+        10,
+        5,
+        false,
+        false,
+        "",
+        false,
+    );
+    assert_eq!(
+        test_structure,
+        Err(String::from("Error: min_length is greater than max_length"))
+    );
+    assert_eq!(all_structures.len(), 0);
+
+    let mut all_structures = Vec::<Structure>::new();
+    Structure::create(
+        &mut all_structures,
+        "summary",
+        "Summary",
+        "text",
+        "",
+        0,
+        0,
+        1,
+        1,
+        false,
+        false,
+        "",
+        false,
+    )
+    .unwrap();
+
+    let test_structure =
+        Structure::update_max_length(&mut all_structures, &"summary".to_string(), 3);
+    assert_eq!(test_structure, Ok(()));
+
+    let test_structure =
+        Structure::update_min_length(&mut all_structures, &"summary2".to_string(), 3);
+    assert_eq!(
+        test_structure,
+        Err(String::from("Error: Structure not found"))
+    );
+
+    let test_structure =
+        Structure::update_min_length(&mut all_structures, &"summary".to_string(), 4);
+    assert_eq!(
+        test_structure,
+        Err(String::from("Error: min_length is greater than max_length"))
+    );
+
+    let test_structure =
+        Structure::update_max_length(&mut all_structures, &"summary".to_string(), 0);
+    assert_eq!(test_structure, Ok(()));
+
+    let test_structure =
+        Structure::update_max_length(&mut all_structures, &"summary".to_string(), 3);
+    assert_eq!(test_structure, Ok(()));
+
+    let test_structure = Structure::create(
+        &mut all_structures,
+        "rating",
+        "Rating",
+        "number",
+        "1",
+        1,
+        5,
+        1,
+        5,
+        false,
+        false,
+        "",
+        false,
+    );
+    assert_eq!(
+        test_structure,
+        Err(String::from(
+            "Error: length limits do not apply to numeric types"
+        ))
+    );
+    assert_eq!(all_structures.len(), 1);
+
+    let test_structure =
+        Structure::update_type(&mut all_structures, &"summary".to_string(), "number");
+    assert_eq!(
+        test_structure,
+        Err(String::from(
+            "Error: length limits do not apply to numeric types"
+        ))
+    );
+
+    let stringified = Structure::to_string(all_structures[0].clone());
+    assert_eq!(
+        stringified,
+        "summary|Summary|text||0|0|false|false||false|1|3"
+    );
+    assert_eq!(
+        Structure::to_string(Structure::from_string(&stringified).unwrap()),
+        stringified
+    );
+}
+
+#[test]
+fn test_structure_types() {
+    let created_at =
+        Structure::from_string("created_at|Created_at|timestamptz||0|0|false|false||false|0|0");
+    assert_eq!(created_at.clone().unwrap().get_type(), Type::TIMESTAMPTZ);
+    assert_eq!(
+        Structure::to_string(created_at.unwrap()),
+        "created_at|Created_at|timestamptz||0|0|false|false||false|0|0"
+    );
+
+    let local = parse_timestamptz("2021-06-01T14:30:00+02:00");
+    assert_eq!(local, Ok((1622550600, 7200)));
+    assert_eq!(
+        parse_timestamptz("2021-06-01T12:30:00Z").map(|(utc, _)| utc),
+        Ok(1622550600)
+    );
+    assert_eq!(
+        format_timestamptz(1622550600, 7200),
+        "2021-06-01T14:30:00+02:00"
+    );
+    assert_eq!(
+        format_timestamptz(-14400, -14400),
+        "1969-12-31T16:00:00-04:00"
+    );
+    assert_eq!(
+        parse_timestamptz("2021-02-29T00:00:00Z"),
+        Err(String::from("Error: timestamptz has an invalid date"))
+    );
+    assert_eq!(
+        parse_timestamptz("2021-06-01 14:30"),
+        Err(String::from("Error: timestamptz has an invalid format"))
+    );
+
+    let mut all_structures = Vec::<Structure>::new();
+    let test_structure = Structure::create(
+        &mut all_structures,
+        "published_at",
+        "Published_at",
+        "timestamptz",
+        "tomorrow",
+        0,
+        0,
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    );
+    assert_eq!(
+        test_structure,
+        Err(String::from(
+            "Error: default_val is not a valid timestamptz"
+        ))
+    );
+
+    let test_structure = Structure::from_string(
+        "published_at|Published_at|timestamptz|tomorrow|0|0|false|false||false|0|0",
+    );
+    assert_eq!(
+        test_structure.map(|structure| structure.get_type()),
+        Err(String::from("Error: Wrong format for Structure data"))
+    );
+
+    let ttl = Structure::from_string("ttl|Ttl|duration||0|0|false|false||false|0|0");
+    assert_eq!(ttl.clone().unwrap().get_type(), Type::DURATION);
+    assert_eq!(
+        Structure::to_string(ttl.unwrap()),
+        "ttl|Ttl|duration||0|0|false|false||false|0|0"
+    );
+
+    assert_eq!(parse_duration("2h30m"), Ok(9000));
+    assert_eq!(parse_duration("1w2d"), Ok(777600));
+    assert_eq!(parse_duration("90s"), Ok(90));
+    assert_eq!(format_duration(9000), "2h30m");
+    assert_eq!(format_duration(90), "1m30s");
+    assert_eq!(
+        parse_duration("30m2h"),
+        Err(String::from("Error: duration has an invalid format"))
+    );
+    assert_eq!(
+        parse_duration(""),
+        Err(String::from("Error: duration has an invalid format"))
+    );
+    assert_eq!(
+        parse_duration("99999999999999999999s"),
+        Err(String::from("Error: duration is too long"))
+    );
+
+    let mut all_structures = Vec::<Structure>::new();
+    let test_structure = Structure::create(
+        &mut all_structures,
+        "ttl",
+        "Ttl",
+        "duration",
+        "2 hours",
+        0,
+        0,
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    );
+    assert_eq!(
+        test_structure,
+        Err(String::from("Error: default_val is not a valid duration"))
+    );
+
+    let test_structure = Structure::create(
+        &mut all_structures,
+        "ttl",
+        "Ttl",
+        "duration",
+        "2h30m",
+        0,
+        0,
+        0,
+        0,
+        false,
+        false,
+        "",
+        false,
+    );
+    assert_eq!(test_structure, Ok(()));
+}
+
 #[test]
 fn test_incorrect_collection() {
+    let mut all_audit_entries = Vec::<AuditEntry>::new();
     let file_name: &str = "data/collection_err_test.txt";
     remove_file(file_name.to_string());
 
@@ -650,6 +1465,8 @@ fn test_incorrect_collection() {
             "konnect",
             "Posts",
             "To store blog posts.",
+            Some(&mut all_audit_entries),
+            "test",
         );
         if let Err(e) = create_collection {
             println!("{}", e);
@@ -662,6 +1479,8 @@ fn test_incorrect_collection() {
             "Title",
             "text",
             "test title",
+            0,
+            0,
             5,
             20,
             false,
@@ -677,6 +1496,8 @@ fn test_incorrect_collection() {
             "Title",
             "text",
             "test title",
+            0,
+            0,
             5,
             20,
             false,
@@ -695,6 +1516,8 @@ fn test_incorrect_collection() {
             "Title",
             "text",
             "test title",
+            0,
+            0,
             5,
             20,
             false,
@@ -748,8 +1571,14 @@ fn test_incorrect_collection() {
             ))
         );
 
-        Collection::set_structures(&mut all_collections, &"posts".to_string(), all_structures)
-            .unwrap();
+        Collection::set_structures(
+            &mut all_collections,
+            &"posts".to_string(),
+            all_structures,
+            Some(&mut all_audit_entries),
+            "test",
+        )
+        .unwrap();
 
         let mut all_custom_structures = Vec::<CustomStructure>::new();
         let mut tmp_structures = Vec::<Structure>::new();
@@ -760,6 +1589,8 @@ fn test_incorrect_collection() {
             "UID",
             "uid",
             "",
+            0,
+            0,
             5,
             20,
             false,
@@ -774,6 +1605,8 @@ fn test_incorrect_collection() {
             "Value",
             "text",
             "",
+            0,
+            0,
             1,
             100,
             false,
@@ -832,6 +1665,8 @@ fn test_incorrect_collection() {
             &mut all_collections,
             &"posts".to_string(),
             all_custom_structures,
+            Some(&mut all_audit_entries),
+            "test",
         )
         .unwrap();
 
@@ -841,28 +1676,45 @@ fn test_incorrect_collection() {
             "konnect",
             "Posts",
             "To store blog posts.",
+            Some(&mut all_audit_entries),
+            "test",
         );
         assert_eq!(
             test_collection,
             Err(String::from("Error: id is already in use"))
         );
 
-        let test_collection =
-            Collection::update_id(&mut all_collections, &"posts2".to_string(), "posts3");
+        let test_collection = Collection::update_id(
+            &mut all_collections,
+            &"posts2".to_string(),
+            "posts3",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(
             test_collection,
             Err(String::from("Error: Collection not found"))
         );
 
-        let test_collection =
-            Collection::update_id(&mut all_collections, &"posts".to_string(), "posts;");
+        let test_collection = Collection::update_id(
+            &mut all_collections,
+            &"posts".to_string(),
+            "posts;",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(
             test_collection,
             Err(String::from("Error: new_id contains an invalid character"))
         );
 
-        let test_collection =
-            Collection::update_project_id(&mut all_collections, &"posts".to_string(), "konnect;");
+        let test_collection = Collection::update_project_id(
+            &mut all_collections,
+            &"posts".to_string(),
+            "konnect;",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(
             test_collection,
             Err(String::from(
@@ -870,8 +1722,13 @@ fn test_incorrect_collection() {
             ))
         );
 
-        let test_collection =
-            Collection::update_name(&mut all_collections, &"posts".to_string(), "Pos>ts");
+        let test_collection = Collection::update_name(
+            &mut all_collections,
+            &"posts".to_string(),
+            "Pos>ts",
+            Some(&mut all_audit_entries),
+            "test",
+        );
         assert_eq!(
             test_collection,
             Err(String::from("Error: name contains an invalid character"))
@@ -881,6 +1738,8 @@ fn test_incorrect_collection() {
             &mut all_collections,
             &"posts".to_string(),
             "To store blog posts@.",
+            Some(&mut all_audit_entries),
+            "test",
         );
         assert_eq!(
             test_collection,
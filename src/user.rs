@@ -1,3 +1,4 @@
+use crate::audit::AuditEntry;
 use crate::encryption::EncryptionKey;
 use crate::io::{fetch_file, save_file};
 use argon2::{self, Config};
@@ -92,6 +93,8 @@ impl User {
         email: &str,
         password: &str,
         role_numeric: u32,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
         return User::create(
             all_users,
@@ -101,6 +104,8 @@ impl User {
             email,
             password,
             role_numeric,
+            audit,
+            actor,
         );
     }
 
@@ -112,7 +117,13 @@ impl User {
         email: &str,
         password: &str,
         role_numeric: u32,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let id = EncryptionKey::generate_uuid(8);
         let uid = id.to_string();
 
@@ -135,7 +146,7 @@ impl User {
         };
         all_users.push(new_user);
 
-        let name_update = Self::update_name(all_users, &uid, first_name, last_name);
+        let name_update = Self::update_name(all_users, &uid, first_name, last_name, None, actor);
         if let Err(e) = name_update {
             has_error = true;
             println!("Error: {}", e);
@@ -143,7 +154,7 @@ impl User {
         }
 
         if !has_error {
-            let username_update = Self::update_username(all_users, &uid, username);
+            let username_update = Self::update_username(all_users, &uid, username, None, actor);
             if let Err(e) = username_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -152,7 +163,7 @@ impl User {
         }
 
         if !has_error {
-            let email_update = Self::update_email(all_users, &uid, email);
+            let email_update = Self::update_email(all_users, &uid, email, None, actor);
             if let Err(e) = email_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -161,7 +172,7 @@ impl User {
         }
 
         if !has_error {
-            let password_update = Self::update_password(all_users, &uid, password);
+            let password_update = Self::update_password(all_users, &uid, password, None, actor);
             if let Err(e) = password_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -170,7 +181,7 @@ impl User {
         }
 
         if !has_error {
-            let role_update = Self::update_role(all_users, &uid, role_numeric);
+            let role_update = Self::update_role(all_users, &uid, role_numeric, None, actor);
             if let Err(e) = role_update {
                 has_error = true;
                 println!("Error: {}", e);
@@ -179,7 +190,7 @@ impl User {
         }
 
         if has_error {
-            let delete_user = Self::delete(all_users, &uid);
+            let delete_user = Self::delete(all_users, &uid, None, actor);
             if let Err(e) = delete_user {
                 println!("Error: {}", e);
             }
@@ -187,6 +198,8 @@ impl User {
             return Err(latest_error);
         }
 
+        AuditEntry::record(audit, actor, "create", "users", &uid);
+
         Ok(())
     }
 
@@ -219,7 +232,13 @@ impl User {
         id: &String,
         first_name: &str,
         last_name: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_user: Option<User> = None;
 
         if !String::from(first_name)
@@ -273,6 +292,8 @@ impl User {
             return Err(String::from("Error: User not found"));
         }
 
+        AuditEntry::record(audit, actor, "update", "users", &format!("{} name", id));
+
         Ok(())
     }
 
@@ -280,7 +301,13 @@ impl User {
         all_users: &mut Vec<User>,
         id: &String,
         username: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_user: Option<User> = None;
 
         for user in all_users.iter() {
@@ -310,10 +337,22 @@ impl User {
             return Err(String::from("Error: User not found"));
         }
 
+        AuditEntry::record(audit, actor, "update", "users", &format!("{} username", id));
+
         Ok(())
     }
 
-    pub fn update_email(all_users: &mut Vec<User>, id: &String, email: &str) -> Result<(), String> {
+    pub fn update_email(
+        all_users: &mut Vec<User>,
+        id: &String,
+        email: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_user: Option<User> = None;
 
         for user in all_users.iter() {
@@ -350,6 +389,8 @@ impl User {
             return Err(String::from("Error: User not found"));
         }
 
+        AuditEntry::record(audit, actor, "update", "users", &format!("{} email", id));
+
         Ok(())
     }
 
@@ -357,7 +398,13 @@ impl User {
         all_users: &mut Vec<User>,
         id: &String,
         password: &str,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_user: Option<User> = None;
 
         if String::from(password.trim()).len() < 7 {
@@ -415,6 +462,8 @@ impl User {
             return Err(String::from("Error: User not found"));
         }
 
+        AuditEntry::record(audit, actor, "update", "users", &format!("{} password", id));
+
         Ok(())
     }
 
@@ -422,7 +471,13 @@ impl User {
         all_users: &mut Vec<User>,
         id: &String,
         role_numeric: u32,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
     ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_user: Option<User> = None;
 
         let role = match role_numeric {
@@ -443,10 +498,21 @@ impl User {
             return Err(String::from("Error: User not found"));
         }
 
+        AuditEntry::record(audit, actor, "update", "users", &format!("{} role", id));
+
         Ok(())
     }
 
-    pub fn delete(all_users: &mut Vec<User>, id: &String) -> Result<(), String> {
+    pub fn delete(
+        all_users: &mut Vec<User>,
+        id: &String,
+        audit: Option<&mut Vec<AuditEntry>>,
+        actor: &str,
+    ) -> Result<(), String> {
+        if let Err(e) = AuditEntry::check_actor(&audit, actor) {
+            return Err(e);
+        }
+
         let mut found_user: Option<User> = None;
 
         for user in all_users.iter_mut() {
@@ -476,6 +542,8 @@ impl User {
 
         *all_users = updated_users;
 
+        AuditEntry::record(audit, actor, "delete", "users", id);
+
         Ok(())
     }
 